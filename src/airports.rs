@@ -1,4 +1,10 @@
 use std::{path::Path, collections::HashMap};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::collections::hash_map::DefaultHasher;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, BufWriter, Read, Write};
 
 use quick_csv;
 use kd_tree::{KdMap};
@@ -31,8 +37,52 @@ pub fn from_csv(path: &str) -> Vec<Airport> {
         .collect()
 }
 
+/// Looks up airports by IATA/ICAO code or by (partial) name, as an
+/// alternative to the coordinate-based `AirportFinder` queries.
+pub struct AirportIndex {
+    by_abr: HashMap<String, usize>,
+    names: Vec<(String, usize)>,
+}
+
+impl AirportIndex {
+    pub fn new(airports: &[Airport]) -> Self {
+        let by_abr = airports.iter()
+            .enumerate()
+            .map(|(i, a)| (a.abr.clone(), i))
+            .collect();
+
+        let names = airports.iter()
+            .enumerate()
+            .map(|(i, a)| (a.name.to_lowercase(), i))
+            .collect();
+
+        Self { by_abr, names }
+    }
+
+    /// Exact lookup by IATA/ICAO code, e.g. `"ARN"`.
+    pub fn by_code(&self, abr: &str) -> Option<usize> {
+        self.by_abr.get(abr).copied()
+    }
+
+    /// Case-insensitive substring search over airport names, e.g. `"heathrow"`.
+    pub fn search_name(&self, query: &str) -> Vec<usize> {
+        let query = query.to_lowercase();
+        self.names.iter()
+            .filter(|(name, _)| name.contains(&query))
+            .map(|(_, i)| *i)
+            .collect()
+    }
+}
+
 pub trait AirportFinder {
     fn closest_ind(&self, lat: f32, long: f32) -> usize;
+
+    /// The `k` nearest airports, closest first.
+    fn k_closest_ind(&self, lat: f32, long: f32, k: usize) -> Vec<usize>;
+
+    /// All airports within `radius_km` great-circle kilometers, in no
+    /// particular order.
+    fn within_radius(&self, lat: f32, long: f32, radius_km: f32) -> Vec<usize>;
 }
 
 pub struct KdTreeAirportFinder {
@@ -55,6 +105,17 @@ impl AirportFinder for KdTreeAirportFinder {
         let point = lat_long_to_point(lat, long);
         self.tree.nearest(&point).expect("embty").item.1
     }
+
+    fn k_closest_ind(&self, lat: f32, long: f32, k: usize) -> Vec<usize> {
+        let point = lat_long_to_point(lat, long);
+        self.tree.nearests(&point, k).into_iter().map(|n| n.item.1).collect()
+    }
+
+    fn within_radius(&self, lat: f32, long: f32, radius_km: f32) -> Vec<usize> {
+        let point = lat_long_to_point(lat, long);
+        let chord = radius_km_to_chord(radius_km);
+        self.tree.within_radius(&point, chord).into_iter().map(|item| item.1).collect()
+    }
 }
 
 
@@ -89,6 +150,14 @@ impl AirportFinder for HashAirportFinder {
             None => panic!("Cound not find bucket, change code to KdTreeFinder")
         }
     }
+
+    fn k_closest_ind(&self, _lat: f32, _long: f32, _k: usize) -> Vec<usize> {
+        panic!("HashAirportFinder only supports exact bucket matches, change code to KdTreeFinder")
+    }
+
+    fn within_radius(&self, _lat: f32, _long: f32, _radius_km: f32) -> Vec<usize> {
+        panic!("HashAirportFinder only supports exact bucket matches, change code to KdTreeFinder")
+    }
 }
 
 
@@ -107,35 +176,687 @@ impl DoubleLoopAirportFinder {
     }
 }
 
-impl AirportFinder for DoubleLoopAirportFinder {
-    fn closest_ind(&self, lat: f32, long: f32) -> usize {
-        let flight_point = lat_long_to_point(lat, long);
-
-        let distances: Vec<f32> = self.airports
+impl DoubleLoopAirportFinder {
+    fn squared_distances(airports: &[[f32; 3]], point: [f32; 3]) -> Vec<f32> {
+        airports
             .iter()
-            .map(|airport_coords| flight_point
+            .map(|airport_coords| point
                     .iter()
                     .zip(airport_coords.iter())
                     .map(|(x, y)| (*x-*y)*(*x-*y))
                     .sum::<f32>()
                 )
-            .collect();
+            .collect()
+    }
+}
+
+impl AirportFinder for DoubleLoopAirportFinder {
+    // Closest airport, i.e. the minimum squared distance. (The previous
+    // `max_by` with a reversed comparator computed the same minimum, just
+    // less legibly; this is the same result spelled out directly.)
+    fn closest_ind(&self, lat: f32, long: f32) -> usize {
+        let flight_point = lat_long_to_point(lat, long);
+        let distances = Self::squared_distances(&self.airports, flight_point);
 
         distances.iter()
             .enumerate()
-            .max_by(|(_, a), (_, b)| b.total_cmp(a))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
             .map(|(index, _)| index)
             .unwrap()
     }
+
+    fn k_closest_ind(&self, lat: f32, long: f32, k: usize) -> Vec<usize> {
+        let flight_point = lat_long_to_point(lat, long);
+        let mut distances: Vec<(usize, f32)> = Self::squared_distances(&self.airports, flight_point)
+            .into_iter()
+            .enumerate()
+            .collect();
+
+        distances.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        distances.into_iter().take(k).map(|(i, _)| i).collect()
+    }
+
+    fn within_radius(&self, lat: f32, long: f32, radius_km: f32) -> Vec<usize> {
+        let flight_point = lat_long_to_point(lat, long);
+        let threshold = radius_km_to_chord(radius_km).powi(2);
+
+        Self::squared_distances(&self.airports, flight_point)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, d)| *d <= threshold)
+            .map(|(i, _)| i)
+            .collect()
+    }
 }
 
 fn lat_long_to_point(lat: f32, long: f32) -> [f32; 3] {
     //TODO if lat/long are very close to read data, then we could just do rounding + hashmap
-    let lo = long.to_radians();
+    // Standard unit-sphere (ECEF-style) embedding: this is an isometry, so
+    // Euclidean distance between two such points is exactly the chord length
+    // for their true great-circle separation. That's what lets
+    // `radius_km_to_chord` turn a great-circle radius into a valid Euclidean
+    // cutoff on these points.
     let la = lat.to_radians();
-    let x = lo.cos()*la.sin();
-    let y = lo.sin()*la.sin();
-    let z = la.cos();
+    let lo = long.to_radians();
+    let x = la.cos() * lo.cos();
+    let y = la.cos() * lo.sin();
+    let z = la.sin();
     [x, y, z]
 }
 
+const EARTH_RADIUS_KM: f32 = 6371.0;
+
+/// Converts a great-circle radius in kilometers to the corresponding chord
+/// (straight-line) distance between the unit-sphere points produced by
+/// `lat_long_to_point`, so a Euclidean cutoff on those points matches a true
+/// great-circle radius.
+fn radius_km_to_chord(radius_km: f32) -> f32 {
+    let central_angle = radius_km / EARTH_RADIUS_KM;
+    2.0 * (central_angle / 2.0).sin()
+}
+
+/// Great-circle distance between two lat/long points, in kilometers.
+fn haversine_km(lat1: f32, long1: f32, lat2: f32, long2: f32) -> f32 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_long = (long2 - long1).to_radians();
+    let lat1 = lat1.to_radians();
+    let lat2 = lat2.to_radians();
+
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * (d_long / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Advances `values` to its next lexicographic permutation in place.
+/// Returns `false` once the sequence is already in descending order, i.e.
+/// all permutations have been exhausted.
+fn next_permutation(values: &mut [usize]) -> bool {
+    if values.len() < 2 {
+        return false;
+    }
+
+    let mut i = values.len() - 1;
+    while i > 0 && values[i - 1] >= values[i] {
+        i -= 1;
+    }
+    if i == 0 {
+        return false;
+    }
+
+    let mut j = values.len() - 1;
+    while values[j] <= values[i - 1] {
+        j -= 1;
+    }
+    values.swap(i - 1, j);
+    values[i..].reverse();
+    true
+}
+
+/// A flight network over the airports in an `Airport` vector, with edges
+/// weighted by the true great-circle leg distance between their endpoints.
+pub struct RouteGraph {
+    edges: Vec<Vec<(usize, f32)>>,
+    coords: Vec<(f32, f32)>,
+}
+
+impl RouteGraph {
+    /// Builds a `RouteGraph` from a routes CSV of `(origin id, destination id)`
+    /// pairs, where the ids refer to `Airport::id`, not vector position.
+    pub fn new(airports: &[Airport], routes_path: &str) -> Self {
+        let id_to_index: HashMap<usize, usize> = airports.iter()
+            .enumerate()
+            .map(|(i, a)| (a.id, i))
+            .collect();
+
+        let coords = airports.iter().map(|a| (a.lat, a.long)).collect();
+        let mut edges = vec![Vec::new(); airports.len()];
+
+        let csv = quick_csv::Csv::from_file(routes_path).expect("Could not find airport route file.");
+        for row in csv.into_iter().skip(1) {
+            let (origin_id, dest_id) = row
+                .expect("Could not parse line in airport route file.")
+                .decode::<(usize, usize)>()
+                .expect("Could not decode line into expected format in airport route file.");
+
+            let (Some(&from), Some(&to)) = (id_to_index.get(&origin_id), id_to_index.get(&dest_id)) else {
+                continue;
+            };
+            let dist = haversine_km(airports[from].lat, airports[from].long, airports[to].lat, airports[to].long);
+            edges[from].push((to, dist));
+        }
+
+        Self { edges, coords }
+    }
+
+    /// Dijkstra's algorithm: minimum-distance path from `from` to `to`.
+    ///
+    /// `from`/`to` are positions in the `Airport` vector this graph was
+    /// built from (as returned by `AirportFinder`/`AirportIndex`), not
+    /// `Airport::id` — the two id-spaces are different.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<(Vec<usize>, f32)> {
+        self.search(from, to, |_| 0.0)
+    }
+
+    /// A* using the straight great-circle distance to `to` as the heuristic.
+    /// That heuristic never overestimates the remaining legs, so the search
+    /// stays optimal while expanding far fewer nodes than plain Dijkstra.
+    ///
+    /// `from`/`to` are vector positions, see `shortest_path`.
+    pub fn shortest_path_astar(&self, from: usize, to: usize) -> Option<(Vec<usize>, f32)> {
+        let (goal_lat, goal_long) = self.coords[to];
+        self.search(from, to, |node| {
+            let (lat, long) = self.coords[node];
+            haversine_km(lat, long, goal_lat, goal_long)
+        })
+    }
+
+    /// Finds the visiting order of `stops` that minimizes total great-circle
+    /// distance. If `fixed_endpoints` is true, `stops[0]` and the last entry
+    /// of `stops` are kept as the tour's start and end and only the stops in
+    /// between are reordered; otherwise all of `stops` may be reordered
+    /// freely. Small waypoint counts are solved exactly by lexicographically
+    /// permuting them; larger ones fall back to a nearest-neighbor greedy
+    /// seed improved with 2-opt.
+    pub fn plan_tour(&self, stops: &[usize], fixed_endpoints: bool) -> (Vec<usize>, f32) {
+        assert!(!stops.is_empty(), "plan_tour needs at least one stop");
+
+        const EXACT_LIMIT: usize = 8;
+
+        let (prefix, suffix, free) = if fixed_endpoints && stops.len() >= 2 {
+            (Some(stops[0]), Some(stops[stops.len() - 1]), stops[1..stops.len() - 1].to_vec())
+        } else {
+            (None, None, stops.to_vec())
+        };
+
+        if free.len() <= EXACT_LIMIT {
+            self.exact_order(prefix, suffix, &free)
+        } else {
+            self.greedy_order(prefix, suffix, &free)
+        }
+    }
+
+    fn exact_order(&self, prefix: Option<usize>, suffix: Option<usize>, free: &[usize]) -> (Vec<usize>, f32) {
+        if free.is_empty() {
+            let order: Vec<usize> = prefix.into_iter().chain(suffix).collect();
+            let dist = self.path_distance(&order);
+            return (order, dist);
+        }
+
+        let mut indices: Vec<usize> = (0..free.len()).collect();
+        let mut best_order = Vec::new();
+        let mut best_dist = f32::INFINITY;
+
+        loop {
+            let candidate: Vec<usize> = prefix.into_iter()
+                .chain(indices.iter().map(|&i| free[i]))
+                .chain(suffix)
+                .collect();
+            let dist = self.path_distance(&candidate);
+            if dist < best_dist {
+                best_dist = dist;
+                best_order = candidate;
+            }
+
+            if !next_permutation(&mut indices) {
+                break;
+            }
+        }
+
+        (best_order, best_dist)
+    }
+
+    fn greedy_order(&self, prefix: Option<usize>, suffix: Option<usize>, free: &[usize]) -> (Vec<usize>, f32) {
+        let mut remaining = free.to_vec();
+
+        let start = match prefix {
+            Some(p) => p,
+            None => remaining.remove(0),
+        };
+        let mut order = vec![start];
+
+        let mut current = start;
+        while !remaining.is_empty() {
+            let (lat, long) = self.coords[current];
+            let (pos, _) = remaining.iter()
+                .enumerate()
+                .map(|(i, &n)| {
+                    let (nlat, nlong) = self.coords[n];
+                    (i, haversine_km(lat, long, nlat, nlong))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .unwrap();
+            current = remaining.remove(pos);
+            order.push(current);
+        }
+
+        if let Some(s) = suffix {
+            order.push(s);
+        }
+
+        let order = self.two_opt(order, prefix.is_some(), suffix.is_some());
+        let dist = self.path_distance(&order);
+        (order, dist)
+    }
+
+    /// Repeatedly reverses segments of `order` whenever doing so shortens
+    /// the tour, leaving any fixed start/end in place.
+    fn two_opt(&self, mut order: Vec<usize>, fix_start: bool, fix_end: bool) -> Vec<usize> {
+        let lo = if fix_start { 1 } else { 0 };
+        let hi = if fix_end { order.len() - 1 } else { order.len() };
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in lo..hi.saturating_sub(1) {
+                for j in (i + 1)..hi {
+                    if self.two_opt_gain(&order, i, j) > 0.0 {
+                        order[i..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Distance saved by reversing `order[i..=j]`, which replaces edge
+    /// `(i-1, i)` with `(i-1, j)` and edge `(j, j+1)` with `(i, j+1)`.
+    /// When `i == 0` or `j + 1 == order.len()` there's no edge on that side
+    /// of the segment to begin with (that end of the path is free to move),
+    /// so only the edge that does exist is counted.
+    fn two_opt_gain(&self, order: &[usize], i: usize, j: usize) -> f32 {
+        let before_start = (i > 0).then(|| self.leg(order[i - 1], order[i]));
+        let before_end = (j + 1 < order.len()).then(|| self.leg(order[j], order[j + 1]));
+
+        if before_start.is_none() && before_end.is_none() {
+            return 0.0; // reversing the whole path changes no edge
+        }
+
+        let after_start = (i > 0).then(|| self.leg(order[i - 1], order[j]));
+        let after_end = (j + 1 < order.len()).then(|| self.leg(order[i], order[j + 1]));
+
+        let before = before_start.unwrap_or(0.0) + before_end.unwrap_or(0.0);
+        let after = after_start.unwrap_or(0.0) + after_end.unwrap_or(0.0);
+
+        before - after
+    }
+
+    fn leg(&self, a: usize, b: usize) -> f32 {
+        let (lat1, long1) = self.coords[a];
+        let (lat2, long2) = self.coords[b];
+        haversine_km(lat1, long1, lat2, long2)
+    }
+
+    fn path_distance(&self, order: &[usize]) -> f32 {
+        order.windows(2)
+            .map(|w| self.leg(w[0], w[1]))
+            .sum()
+    }
+
+    fn search<F: Fn(usize) -> f32>(&self, from: usize, to: usize, heuristic: F) -> Option<(Vec<usize>, f32)> {
+        let n = self.edges.len();
+        let mut dist = vec![f32::INFINITY; n];
+        let mut prev = vec![None; n];
+        let mut heap = BinaryHeap::new();
+
+        dist[from] = 0.0;
+        heap.push(HeapEntry { priority: heuristic(from), cost: 0.0, node: from });
+
+        while let Some(HeapEntry { cost, node, .. }) = heap.pop() {
+            if cost > dist[node] {
+                continue; // stale entry: a shorter path to `node` was already found
+            }
+            if node == to {
+                break;
+            }
+
+            for &(next, weight) in &self.edges[node] {
+                let next_cost = cost + weight;
+                if next_cost < dist[next] {
+                    dist[next] = next_cost;
+                    prev[next] = Some(node);
+                    heap.push(HeapEntry { priority: next_cost + heuristic(next), cost: next_cost, node: next });
+                }
+            }
+        }
+
+        if dist[to].is_infinite() {
+            return None;
+        }
+
+        let mut path = vec![to];
+        while let Some(p) = prev[*path.last().unwrap()] {
+            path.push(p);
+        }
+        path.reverse();
+
+        Some((path, dist[to]))
+    }
+}
+
+/// Min-heap frontier entry, ordered by ascending `priority` (cumulative cost
+/// plus, for A*, the heuristic estimate to the goal). `cost` is the plain
+/// cumulative cost (no heuristic), kept alongside `priority` so a popped
+/// entry can be checked against `dist[node]` and skipped if stale.
+struct HeapEntry {
+    priority: f32,
+    cost: f32,
+    node: usize,
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.total_cmp(&self.priority)
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Routes between airports whose direct distance exceeds any aircraft's
+/// range, by chaining hops no longer than `max_leg_km`. Neighbors are
+/// discovered lazily via `AirportFinder::within_radius` instead of a
+/// materialized all-pairs graph, so large airport sets stay tractable.
+pub struct JumpRangeRouter<'a, F: AirportFinder> {
+    airports: &'a Vec<Airport>,
+    finder: &'a F,
+    max_leg_km: f32,
+}
+
+impl<'a, F: AirportFinder> JumpRangeRouter<'a, F> {
+    pub fn new(airports: &'a Vec<Airport>, finder: &'a F, max_leg_km: f32) -> Self {
+        Self { airports, finder, max_leg_km }
+    }
+
+    /// Minimum-hop chain of airports from `from` to `to`, via BFS over the
+    /// implicit within-range graph.
+    pub fn min_hops(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut visited = vec![false; self.airports.len()];
+        let mut prev = vec![None; self.airports.len()];
+        let mut queue = VecDeque::new();
+
+        visited[from] = true;
+        queue.push_back(from);
+
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                return Some(self.reconstruct(&prev, to));
+            }
+            for next in self.neighbors(node) {
+                if !visited[next] {
+                    visited[next] = true;
+                    prev[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Minimum-distance chain of airports from `from` to `to`, via Dijkstra
+    /// over the implicit within-range graph.
+    pub fn min_distance(&self, from: usize, to: usize) -> Option<(Vec<usize>, f32)> {
+        let n = self.airports.len();
+        let mut dist = vec![f32::INFINITY; n];
+        let mut prev = vec![None; n];
+        let mut heap = BinaryHeap::new();
+
+        dist[from] = 0.0;
+        heap.push(HeapEntry { priority: 0.0, cost: 0.0, node: from });
+
+        while let Some(HeapEntry { cost, node, .. }) = heap.pop() {
+            if cost > dist[node] {
+                continue; // stale entry: a shorter path to `node` was already found
+            }
+            if node == to {
+                break;
+            }
+
+            for next in self.neighbors(node) {
+                let leg = haversine_km(
+                    self.airports[node].lat, self.airports[node].long,
+                    self.airports[next].lat, self.airports[next].long,
+                );
+                let next_cost = cost + leg;
+                if next_cost < dist[next] {
+                    dist[next] = next_cost;
+                    prev[next] = Some(node);
+                    heap.push(HeapEntry { priority: next_cost, cost: next_cost, node: next });
+                }
+            }
+        }
+
+        if dist[to].is_infinite() {
+            return None;
+        }
+
+        Some((self.reconstruct(&prev, to), dist[to]))
+    }
+
+    fn neighbors(&self, node: usize) -> Vec<usize> {
+        let airport = &self.airports[node];
+        self.finder.within_radius(airport.lat, airport.long, self.max_leg_km)
+            .into_iter()
+            .filter(|&i| i != node)
+            .collect()
+    }
+
+    fn reconstruct(&self, prev: &[Option<usize>], to: usize) -> Vec<usize> {
+        let mut path = vec![to];
+        while let Some(p) = prev[*path.last().unwrap()] {
+            path.push(p);
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// A precomputed cache of the airport vector and `RouteGraph` adjacency,
+/// serialized to a compact binary file so repeated runs can skip re-parsing
+/// the source CSVs and recomputing haversine legs. Keyed by a hash of the
+/// CSVs it was built from, so a stale cache is detected rather than silently
+/// reused.
+pub struct RouteCache {
+    input_hash: u64,
+    airports: Vec<Airport>,
+    edges: Vec<Vec<(usize, f32)>>,
+}
+
+impl RouteCache {
+    /// Parses the airport and route CSVs and bundles the result into a
+    /// cache keyed by a hash of their contents.
+    pub fn precompute(airports_path: &str, routes_path: &str) -> Self {
+        let input_hash = Self::hash_inputs(airports_path, routes_path);
+        let airports = from_csv(airports_path);
+        let graph = RouteGraph::new(&airports, routes_path);
+        Self { input_hash, airports, edges: graph.edges }
+    }
+
+    /// Hashes the contents of the airport/route CSVs, without parsing them.
+    /// Cheap enough to call before `load` to check for staleness, unlike
+    /// `precompute` which fully re-parses both files.
+    pub fn hash_inputs(airports_path: &str, routes_path: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        std::fs::read(airports_path).expect("Could not read airport location file for hashing.").hash(&mut hasher);
+        std::fs::read(routes_path).expect("Could not read airport route file for hashing.").hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Writes this cache to `path` as a compact, length-prefixed binary file.
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let file = File::create(path)?;
+        let mut w = BufWriter::new(file);
+
+        w.write_all(&self.input_hash.to_le_bytes())?;
+
+        w.write_all(&(self.airports.len() as u64).to_le_bytes())?;
+        for airport in &self.airports {
+            write_string(&mut w, &airport.name)?;
+            write_string(&mut w, &airport.abr)?;
+            w.write_all(&airport.lat.to_le_bytes())?;
+            w.write_all(&airport.long.to_le_bytes())?;
+            w.write_all(&(airport.id as u64).to_le_bytes())?;
+        }
+
+        w.write_all(&(self.edges.len() as u64).to_le_bytes())?;
+        for adj in &self.edges {
+            w.write_all(&(adj.len() as u64).to_le_bytes())?;
+            for &(to, dist) in adj {
+                w.write_all(&(to as u64).to_le_bytes())?;
+                w.write_all(&dist.to_le_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads a cache file written by `save`. Returns `Ok(None)` if the
+    /// stored input hash doesn't match `expected_input_hash`, i.e. the cache
+    /// is stale and the caller should fall back to `precompute`.
+    pub fn load(path: &str, expected_input_hash: u64) -> std::io::Result<Option<Self>> {
+        let file = File::open(path)?;
+        let mut r = BufReader::new(file);
+
+        let input_hash = read_u64(&mut r)?;
+        if input_hash != expected_input_hash {
+            return Ok(None);
+        }
+
+        let airport_count = read_u64(&mut r)? as usize;
+        let mut airports = Vec::with_capacity(airport_count);
+        for _ in 0..airport_count {
+            let name = read_string(&mut r)?;
+            let abr = read_string(&mut r)?;
+            let lat = read_f32(&mut r)?;
+            let long = read_f32(&mut r)?;
+            let id = read_u64(&mut r)? as usize;
+            airports.push(Airport { name, abr, lat, long, id });
+        }
+
+        let node_count = read_u64(&mut r)? as usize;
+        let mut edges = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            let edge_count = read_u64(&mut r)? as usize;
+            let mut adj = Vec::with_capacity(edge_count);
+            for _ in 0..edge_count {
+                let to = read_u64(&mut r)? as usize;
+                let dist = read_f32(&mut r)?;
+                adj.push((to, dist));
+            }
+            edges.push(adj);
+        }
+
+        Ok(Some(Self { input_hash, airports, edges }))
+    }
+
+    /// The hash this cache was built from, for comparing against a freshly
+    /// computed `hash_inputs` before deciding whether to reuse it.
+    pub fn input_hash(&self) -> u64 {
+        self.input_hash
+    }
+
+    /// Rebuilds the `KdTreeAirportFinder` and `RouteGraph` from the cached
+    /// data, without re-reading or re-parsing the source CSVs.
+    pub fn into_finder_and_graph(self) -> (KdTreeAirportFinder, RouteGraph) {
+        let finder = KdTreeAirportFinder::new(&self.airports);
+        let coords = self.airports.iter().map(|a| (a.lat, a.long)).collect();
+        let graph = RouteGraph { edges: self.edges, coords };
+        (finder, graph)
+    }
+}
+
+fn write_string<W: Write>(w: &mut W, s: &str) -> std::io::Result<()> {
+    let bytes = s.as_bytes();
+    w.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    w.write_all(bytes)
+}
+
+fn read_string<R: Read>(r: &mut R) -> std::io::Result<String> {
+    let len = read_u64(r)? as usize;
+    let mut buf = vec![0u8; len];
+    r.read_exact(&mut buf)?;
+    String::from_utf8(buf).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn read_u64<R: Read>(r: &mut R) -> std::io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_f32<R: Read>(r: &mut R) -> std::io::Result<f32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(f32::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_cache(input_hash: u64) -> RouteCache {
+        RouteCache {
+            input_hash,
+            airports: vec![
+                Airport { name: "Arlanda".into(), abr: "ARN".into(), lat: 59.65, long: 17.92, id: 10 },
+                Airport { name: "Heathrow".into(), abr: "LHR".into(), lat: 51.47, long: -0.45, id: 20 },
+            ],
+            edges: vec![vec![(1, 1462.2)], vec![]],
+        }
+    }
+
+    #[test]
+    fn save_load_roundtrip_preserves_airports_and_edges() {
+        let path_buf = std::env::temp_dir().join("route_cache_roundtrip_test.bin");
+        let path = path_buf.to_str().unwrap();
+
+        let cache = sample_cache(42);
+        cache.save(path).expect("save should succeed");
+
+        let loaded = RouteCache::load(path, 42)
+            .expect("load should succeed")
+            .expect("matching hash should not be reported as stale");
+
+        assert_eq!(loaded.input_hash, cache.input_hash);
+        assert_eq!(loaded.edges, cache.edges);
+        assert_eq!(loaded.airports.len(), cache.airports.len());
+        for (a, b) in loaded.airports.iter().zip(cache.airports.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.abr, b.abr);
+            assert_eq!(a.lat, b.lat);
+            assert_eq!(a.long, b.long);
+            assert_eq!(a.id, b.id);
+        }
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn load_detects_stale_hash() {
+        let path_buf = std::env::temp_dir().join("route_cache_stale_test.bin");
+        let path = path_buf.to_str().unwrap();
+
+        let cache = sample_cache(7);
+        cache.save(path).expect("save should succeed");
+
+        let loaded = RouteCache::load(path, 8).expect("load should succeed");
+        assert!(loaded.is_none(), "mismatched hash should be reported as stale");
+
+        std::fs::remove_file(path).ok();
+    }
+}
+